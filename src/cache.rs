@@ -0,0 +1,123 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::Node;
+
+/// Interns `Node`s so that identical subtrees share one allocation instead
+/// of each being its own deep clone — the "green tree" idea of structural
+/// sharing over owned, immutable nodes.
+///
+/// A subtree is considered identical to an already-cached one when its name
+/// matches and every child is *itself* the same cached subtree (compared by
+/// pointer, not by value). Because interning happens bottom-up — children
+/// are always interned before their parent — this collapses repeated shapes
+/// (many empty directories, duplicate `node_modules` subtrees, ...) to a
+/// single shared `children` allocation.
+#[derive(Default)]
+pub struct NodeCache {
+    table: RefCell<HashMap<(String, Vec<usize>), Node>>,
+}
+
+impl NodeCache {
+    pub fn new() -> NodeCache {
+        NodeCache::default()
+    }
+
+    pub(crate) fn intern(&self, name: String, children: Vec<Node>) -> Node {
+        let key = (name.clone(), children.iter().map(Self::identity).collect());
+
+        if let Some(cached) = self.table.borrow().get(&key) {
+            return cached.clone();
+        }
+
+        let node = Node { name, children: Rc::from(children) };
+        self.table.borrow_mut().insert(key, node.clone());
+        node
+    }
+
+    fn identity(node: &Node) -> usize {
+        Rc::as_ptr(&node.children) as *const () as usize
+    }
+}
+
+/// Builds a [`Node`] tree bottom-up through a [`NodeCache`], so that the
+/// finished tree shares allocations between identical subtrees wherever
+/// `start_node`/`finish_node` pairs describe the same shape twice.
+pub struct TreeBuilder<'a> {
+    cache: &'a NodeCache,
+    stack: Vec<(String, Vec<Node>)>,
+    root: Option<Node>,
+}
+
+impl<'a> TreeBuilder<'a> {
+    pub fn new(cache: &'a NodeCache) -> TreeBuilder<'a> {
+        TreeBuilder { cache, stack: Vec::new(), root: None }
+    }
+
+    /// Opens a node that will hold children until the matching `finish_node`.
+    pub fn start_node(&mut self, name: impl Into<String>) {
+        self.stack.push((name.into(), Vec::new()));
+    }
+
+    /// Adds a childless node under whichever node is currently open.
+    pub fn leaf(&mut self, name: impl Into<String>) {
+        let node = self.cache.intern(name.into(), Vec::new());
+        self.attach(node);
+    }
+
+    /// Closes the most recently opened node, interning it and attaching it
+    /// to its parent (or, if the stack is now empty, making it the root).
+    pub fn finish_node(&mut self) {
+        let (name, children) = self.stack.pop().expect("finish_node with no open start_node");
+        let node = self.cache.intern(name, children);
+        self.attach(node);
+    }
+
+    fn attach(&mut self, node: Node) {
+        match self.stack.last_mut() {
+            Some((_, children)) => children.push(node),
+            None => self.root = Some(node),
+        }
+    }
+
+    /// Consumes the builder, returning the finished root.
+    pub fn build(self) -> Node {
+        self.root.expect("TreeBuilder::build called before the root node was finished")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_nested_tree() {
+        let cache = NodeCache::new();
+        let mut builder = TreeBuilder::new(&cache);
+
+        builder.start_node("parent");
+        builder.leaf("child 1");
+        builder.start_node("child 2");
+        builder.leaf("grandkid 2 1");
+        builder.finish_node();
+        builder.finish_node();
+
+        let tree = builder.build();
+        assert_eq!(tree.to_string(), "parent\n+-- child 1\n`-- child 2\n    `-- grandkid 2 1");
+    }
+
+    #[test]
+    fn interns_identical_subtrees() {
+        let cache = NodeCache::new();
+        let mut builder = TreeBuilder::new(&cache);
+
+        builder.start_node("root");
+        builder.leaf("empty");
+        builder.leaf("empty");
+        builder.finish_node();
+
+        let tree = builder.build();
+        assert_eq!(Rc::as_ptr(&tree.children[0].children), Rc::as_ptr(&tree.children[1].children));
+    }
+}
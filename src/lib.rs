@@ -2,6 +2,27 @@ use std::io;
 use std::fmt;
 use std::fs;
 use std::path::Path;
+use std::rc::Rc;
+
+mod parse;
+pub use parse::ParseError;
+
+mod style;
+pub use style::Style;
+
+mod query;
+pub use query::Descendants;
+
+mod cache;
+pub use cache::{NodeCache, TreeBuilder};
+
+mod walk;
+pub use walk::{SortMode, WalkOptions};
+
+// requires serde's "rc" feature, so that `Rc<[Node]>` (de)serializes as a
+// plain sequence instead of needing a manual impl
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Copy)]
 pub enum Indent {
@@ -22,16 +43,20 @@ impl fmt::Display for Indent {
     }
 }
 
-#[derive(Debug)]
+// `children` lives behind an `Rc` so that cloning a node — and, recursively,
+// an entire tree — is a pointer bump rather than a deep copy, and so that
+// `NodeCache` can intern repeated subtrees as one shared allocation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Node {
     pub name: String,
-    pub children: Vec<Node>
+    pub children: Rc<[Node]>
 }
 
 impl fmt::Display for Node {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let prefix = vec![];
-        f.write_str(self.render(&prefix, true).as_ref())
+        f.write_str(self.render(&Style::ascii(), &prefix, true).as_ref())
     }
 }
 
@@ -51,13 +76,13 @@ impl Node {
     // |  `- node12
     // `- node2
 
-    fn render(&self, prefix: &Vec<Indent>, is_last: bool) -> String {
+    fn render(&self, style: &Style, prefix: &[Indent], is_last: bool) -> String {
         let mut result = String::new();
 
         // add current prefix
         for indent in prefix.iter() {
-            result.push_str(&indent.to_string());
-            result.push(' ');
+            result.push_str(style.connector(*indent));
+            result.push_str(&style.separator);
         }
 
         // render the name
@@ -66,7 +91,7 @@ impl Node {
         // render children
         for (i, child) in self.children.iter().enumerate() {
             // introduce new prefix for children
-            let mut child_prefix = prefix.clone();
+            let mut child_prefix = prefix.to_vec();
 
             if let Some(last) = child_prefix.last_mut() {
                 *last = if is_last { Indent::Blank } else { Indent::Uplink };
@@ -79,14 +104,21 @@ impl Node {
 
             // and recurse
             result.push('\n');
-            result.push_str(&child.render(&child_prefix, is_last_inner))
+            result.push_str(&child.render(style, &child_prefix, is_last_inner))
         }
 
         result
     }
 
+    /// Renders the tree using a custom [`Style`] instead of the ASCII
+    /// glyphs `Display` defaults to.
+    pub fn render_styled(&self, style: &Style) -> String {
+        let prefix = vec![];
+        self.render(style, &prefix, true)
+    }
+
     pub fn singleton(name: String) -> Node {
-        Node { name, children: vec![] }
+        Node { name, children: Rc::from(Vec::new()) }
     }
 
     pub fn flat(name: String, children: Vec<String>) -> Node {
@@ -94,14 +126,16 @@ impl Node {
             Self::singleton(name)
         }
         else {
-            let nodes = children.iter()
+            let nodes: Vec<Node> = children.iter()
                                 .map(|x| Self::singleton(x.to_string()))
                                 .collect();
-            Node { name, children: nodes }
+            Node { name, children: Rc::from(nodes) }
         }
     }
 
-    fn traverse(path: &Path, node: &mut Node) -> io::Result<()> {
+    fn traverse(path: &Path, name: &str, builder: &mut TreeBuilder) -> io::Result<()> {
+        builder.start_node(name);
+
         if path.is_dir() {
             let children = fs::read_dir(path)?;
             for child in children {
@@ -111,21 +145,64 @@ impl Node {
                                            .map_or_else(|| child_path.display().to_string(),
                                                         |n| n.to_string_lossy().to_string());
 
-                let mut child_node = Self::singleton(child_name);
-                Self::traverse(&child_path, &mut child_node)?;
-                node.children.push(child_node);
+                Self::traverse(&child_path, &child_name, builder)?;
             }
         }
 
+        builder.finish_node();
         Ok(())
     }
 
     pub fn from_path(path: &Path) -> io::Result<Node> {
+        let cache = NodeCache::new();
+        let mut builder = TreeBuilder::new(&cache);
         let s = path.display();
-        let mut node = Self::singleton(s.to_string());
-        Self::traverse(path, &mut node)?;
+        Self::traverse(path, &s.to_string(), &mut builder)?;
 
-        Ok(node)
+        Ok(builder.build())
+    }
+
+    /// Merges a flat list of paths into a single tree, collapsing shared
+    /// leading components trie-style (so `a/b/c` and `a/b/d` share the `a/b`
+    /// subtree). The result is a synthetic, unnamed root whose children are
+    /// the top-level path components.
+    pub fn from_paths(paths: &[&Path]) -> Node {
+        // Mutable staging tree: `children` needs to grow in place while the
+        // trie is built, which an `Rc<[Node]>` can't do, so assemble it here
+        // and convert to `Node`s bottom-up once the shape is final.
+        struct Staging {
+            name: String,
+            children: Vec<Staging>,
+        }
+
+        fn finish(staging: Staging) -> Node {
+            Node {
+                name: staging.name,
+                children: Rc::from(staging.children.into_iter().map(finish).collect::<Vec<_>>()),
+            }
+        }
+
+        let mut root = Staging { name: String::new(), children: vec![] };
+
+        for path in paths {
+            let mut current = &mut root;
+
+            for component in path.components() {
+                let name = component.as_os_str().to_string_lossy().to_string();
+
+                let idx = match current.children.iter().position(|c| c.name == name) {
+                    Some(i) => i,
+                    None => {
+                        current.children.push(Staging { name, children: vec![] });
+                        current.children.len() - 1
+                    }
+                };
+
+                current = &mut current.children[idx];
+            }
+        }
+
+        finish(root)
     }
 }
 
@@ -139,24 +216,28 @@ mod tests {
         assert_eq!(singleton.to_string(), "name".to_string());
     }
 
+    fn tree() -> Node {
+        Node {
+            name: "parent".to_string(),
+            children: Rc::from(vec![
+                Node { name: "child 1".to_string(), children: Rc::from(vec![
+                    Node{ name: "grandkid 1 1".to_string(), children: Rc::from(vec![
+                        Node{ name: "greatgrandkid 1 1 1".to_string(), children: Rc::from(vec![])}
+                    ])},
+                    Node{ name: "grandkid 1 2".to_string(), children: Rc::from(vec![])},
+                    Node{ name: "grandkid 1 3".to_string(), children: Rc::from(vec![])}
+                ])},
+                Node { name: "child 2".to_string(), children: Rc::from(vec![])},
+                Node { name: "child 3".to_string(), children: Rc::from(vec![
+                    Node{ name: "grandkid 3 1".to_string(), children: Rc::from(vec![])}
+                ])}
+            ])
+        }
+    }
+
     #[test]
     fn four_levels() {
-        let tree = Node {
-            name: "parent".to_string(),
-            children: vec![
-                Node { name: "child 1".to_string(), children: vec![
-                    Node{ name: "grandkid 1 1".to_string(), children: vec![
-                        Node{ name: "greatgrandkid 1 1 1".to_string(), children: vec![]}
-                    ]},
-                    Node{ name: "grandkid 1 2".to_string(), children: vec![]},
-                    Node{ name: "grandkid 1 3".to_string(), children: vec![]}
-                ]},
-                Node { name: "child 2".to_string(), children: vec![]},
-                Node { name: "child 3".to_string(), children: vec![
-                    Node{ name: "grandkid 3 1".to_string(), children: vec![]}
-                ]}
-            ]
-        };
+        let tree = tree();
 
         let rendered =
             "parent
@@ -170,4 +251,49 @@ mod tests {
     `-- grandkid 3 1";
         assert_eq!(tree.to_string(), rendered.to_string());
     }
+
+    #[test]
+    fn unicode_style() {
+        let tree = Node { name: "parent".to_string(), children: Rc::from(vec![
+            Node::singleton("child 1".to_string()),
+            Node::singleton("child 2".to_string()),
+        ])};
+
+        let rendered =
+            "parent
+├── child 1
+└── child 2";
+        assert_eq!(tree.render_styled(&Style::unicode()), rendered.to_string());
+    }
+
+    #[test]
+    fn from_paths_merges_shared_prefixes() {
+        let tree = Node::from_paths(&[
+            Path::new("a/b/c"),
+            Path::new("a/b/d"),
+            Path::new("a/e"),
+        ]);
+
+        assert_eq!(tree.children.len(), 1);
+        let a = &tree.children[0];
+        assert_eq!(a.name, "a");
+        assert_eq!(a.children.len(), 2);
+
+        let b = a.children.iter().find(|c| c.name == "b").unwrap();
+        assert_eq!(b.children.len(), 2);
+        assert!(b.children.iter().any(|c| c.name == "c"));
+        assert!(b.children.iter().any(|c| c.name == "d"));
+
+        assert!(a.children.iter().any(|c| c.name == "e"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let tree = tree();
+
+        let json = serde_json::to_string(&tree).unwrap();
+        let restored: Node = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.to_string(), tree.to_string());
+    }
 }
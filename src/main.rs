@@ -13,10 +13,7 @@ fn main() -> Result<(), io::Error> {
                 continue;
             }
             let path = Path::new(dir);
-            let tree = match Node::from_path(&path) {
-                Ok(node) => node,
-                Err(e) => return Err(e),
-            };
+            let tree = Node::from_path(path)?;
             println!("{}", tree);
         }
 
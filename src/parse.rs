@@ -0,0 +1,195 @@
+use std::fmt;
+
+use crate::Node;
+
+/// Errors produced while reconstructing a [`Node`] tree from indented text.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line dedented to a column that doesn't match any enclosing indent level.
+    InconsistentIndent { line: usize, column: usize },
+    /// A line's leading whitespace mixes tabs and spaces.
+    MixedIndentChars { line: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ParseError::InconsistentIndent { line, column } =>
+                write!(f, "line {}: dedent to column {} matches no enclosing indent level", line, column),
+            ParseError::MixedIndentChars { line } =>
+                write!(f, "line {}: leading whitespace mixes tabs and spaces", line),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+enum Token {
+    Indent,
+    Dedent,
+    Text(String),
+}
+
+fn lex(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    // The base level is whatever the first non-blank line's column is, not
+    // necessarily 0 — text may be (uniformly) indented to start with, e.g.
+    // piped output re-indented by a wrapper script.
+    let mut indent_stack: Vec<usize> = Vec::new();
+
+    for (i, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let leading = &line[..line.len() - line.trim_start().len()];
+        if leading.contains('\t') && leading.contains(' ') {
+            return Err(ParseError::MixedIndentChars { line: i + 1 });
+        }
+        let column = leading.len();
+
+        if indent_stack.is_empty() {
+            indent_stack.push(column);
+        }
+        else if column > *indent_stack.last().unwrap() {
+            indent_stack.push(column);
+            tokens.push(Token::Indent);
+        }
+        else {
+            // indent_stack[0] is the document's base level and is never
+            // popped — dedenting below it is inconsistent indentation, not
+            // a legal return to some enclosing level.
+            while indent_stack.len() > 1 && column < *indent_stack.last().unwrap() {
+                indent_stack.pop();
+                tokens.push(Token::Dedent);
+            }
+            if column != *indent_stack.last().unwrap() {
+                return Err(ParseError::InconsistentIndent { line: i + 1, column });
+            }
+        }
+
+        tokens.push(Token::Text(line.trim().to_string()));
+    }
+
+    while indent_stack.len() > 1 {
+        indent_stack.pop();
+        tokens.push(Token::Dedent);
+    }
+
+    Ok(tokens)
+}
+
+// Consumes one node (and, recursively, its indented children) starting at
+// `*pos`, which must point at a `Token::Text`.
+fn build(tokens: &[Token], pos: &mut usize) -> Node {
+    let name = match &tokens[*pos] {
+        Token::Text(s) => s.clone(),
+        _ => unreachable!("build() must start on a Token::Text"),
+    };
+    *pos += 1;
+
+    let mut children = Vec::new();
+    if matches!(tokens.get(*pos), Some(Token::Indent)) {
+        *pos += 1;
+        while !matches!(tokens.get(*pos), None | Some(Token::Dedent)) {
+            children.push(build(tokens, pos));
+        }
+        if matches!(tokens.get(*pos), Some(Token::Dedent)) {
+            *pos += 1;
+        }
+    }
+
+    Node { name, children: children.into() }
+}
+
+impl Node {
+    /// Parses whitespace-indented outline text back into a tree. Only leading
+    /// whitespace counts as indentation: the connector glyphs written by
+    /// [`Display`](std::fmt::Display) and [`Style::ascii`](crate::Style::ascii)
+    /// or [`Style::unicode`](crate::Style::unicode) are not stripped, so this
+    /// inverts [`Node::render_styled`] only for a style whose connectors are
+    /// all whitespace. Blank lines are ignored; a line's indentation must
+    /// either match, exceed, or fall back onto an already-open level, and may
+    /// not mix tabs and spaces.
+    ///
+    /// If the text has more than one top-level line, the result is a
+    /// synthetic, unnamed root holding them all as children — mirroring
+    /// [`Node::from_paths`].
+    pub fn from_indented_str(input: &str) -> Result<Node, ParseError> {
+        let tokens = lex(input)?;
+
+        let mut roots = Vec::new();
+        let mut pos = 0;
+        while pos < tokens.len() {
+            roots.push(build(&tokens, &mut pos));
+        }
+
+        Ok(if roots.len() == 1 {
+            roots.remove(0)
+        }
+        else {
+            Node { name: String::new(), children: roots.into() }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_plain_indented_outline() {
+        // `render`'s own output uses connector glyphs rather than plain
+        // whitespace for indentation, so the round-trip with `from_indented_str`
+        // is approximate, not exact; this is the shape a hand-written outline
+        // or `find` output would actually take.
+        let input = "parent\n  child 1\n  child 2\n    grandkid 2 1";
+        let parsed = Node::from_indented_str(input).unwrap();
+
+        assert_eq!(parsed.name, "parent");
+        assert_eq!(parsed.children.len(), 2);
+        assert_eq!(parsed.children[0].name, "child 1");
+        assert_eq!(parsed.children[1].name, "child 2");
+        assert_eq!(parsed.children[1].children.len(), 1);
+        assert_eq!(parsed.children[1].children[0].name, "grandkid 2 1");
+    }
+
+    #[test]
+    fn handles_a_uniformly_indented_first_line() {
+        // The base indent level is whatever the first line happens to use,
+        // not necessarily column 0.
+        let parsed = Node::from_indented_str("  child\n  child2").unwrap();
+
+        assert_eq!(parsed.name, "");
+        assert_eq!(parsed.children.len(), 2);
+        assert_eq!(parsed.children[0].name, "child");
+        assert_eq!(parsed.children[1].name, "child2");
+    }
+
+    #[test]
+    fn rejects_a_dedent_below_a_nonzero_base() {
+        // The document's base indent (2, from its first line) must act as a
+        // floor: a line dedenting below it can't match any enclosing level.
+        let input = "  child\n    grandkid\nbase";
+        assert_eq!(Node::from_indented_str(input).unwrap_err(), ParseError::InconsistentIndent { line: 3, column: 0 });
+    }
+
+    #[test]
+    fn skips_blank_lines() {
+        let input = "parent\n  child 1\n\n  child 2\n\n";
+        let parsed = Node::from_indented_str(input).unwrap();
+        assert_eq!(parsed.children.len(), 2);
+    }
+
+    #[test]
+    fn rejects_mixed_tabs_and_spaces() {
+        let input = "parent\n \tchild";
+        assert_eq!(Node::from_indented_str(input).unwrap_err(), ParseError::MixedIndentChars { line: 2 });
+    }
+
+    #[test]
+    fn rejects_inconsistent_dedent() {
+        let input = "parent\n    child 1\n  child 2";
+        assert_eq!(Node::from_indented_str(input).unwrap_err(), ParseError::InconsistentIndent { line: 3, column: 2 });
+    }
+}
@@ -0,0 +1,127 @@
+use crate::Node;
+
+/// A pre-order, depth-first iterator over a [`Node`] and its descendants,
+/// including the starting node itself. Built on an explicit stack rather
+/// than recursion, so it doesn't blow the call stack on deep trees.
+pub struct Descendants<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<&'a Node> {
+        let node = self.stack.pop()?;
+
+        // push in reverse so children are popped left-to-right
+        for child in node.children.iter().rev() {
+            self.stack.push(child);
+        }
+
+        Some(node)
+    }
+}
+
+impl Node {
+    /// Pre-order iterator over this node and all of its descendants.
+    pub fn descendants(&self) -> Descendants<'_> {
+        Descendants { stack: vec![self] }
+    }
+
+    /// Iterator over the childless nodes reachable from this one.
+    pub fn leaves(&self) -> impl Iterator<Item = &Node> {
+        self.descendants().filter(|n| n.children.is_empty())
+    }
+
+    /// Height of the subtree rooted at this node: 1 for a leaf, and one more
+    /// than the deepest child otherwise.
+    pub fn depth(&self) -> usize {
+        1 + self.children.iter().map(Node::depth).max().unwrap_or(0)
+    }
+
+    /// Returns the first descendant (pre-order, self included) matching
+    /// `predicate`.
+    pub fn find(&self, predicate: impl Fn(&Node) -> bool) -> Option<&Node> {
+        self.descendants().find(|n| predicate(n))
+    }
+
+    /// Builds a new tree with the same shape as this one, with every name
+    /// passed through `f`.
+    pub fn map_names(&self, f: impl Fn(&str) -> String) -> Node {
+        // Recurse through a `&dyn Fn`, not the generic `f` directly — each
+        // recursive call into a generic `impl Fn` would otherwise monomorphize
+        // a new instantiation (F, &F, &&F, ...) without end, and the crate
+        // would hit the compiler's recursion limit and fail to build.
+        fn go(node: &Node, f: &dyn Fn(&str) -> String) -> Node {
+            Node {
+                name: f(&node.name),
+                children: node.children.iter().map(|c| go(c, f)).collect::<Vec<_>>().into(),
+            }
+        }
+
+        go(self, &f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    fn tree() -> Node {
+        Node { name: "parent".to_string(), children: Rc::from(vec![
+            Node { name: "child 1".to_string(), children: Rc::from(vec![
+                Node::singleton("grandkid 1 1".to_string()),
+            ])},
+            Node::singleton("child 2".to_string()),
+        ])}
+    }
+
+    #[test]
+    fn descendants_are_preorder() {
+        let names: Vec<_> = tree().descendants().map(|n| n.name.clone()).collect();
+        assert_eq!(names, vec!["parent", "child 1", "grandkid 1 1", "child 2"]);
+    }
+
+    #[test]
+    fn leaves_skips_internal_nodes() {
+        let names: Vec<_> = tree().leaves().map(|n| n.name.clone()).collect();
+        assert_eq!(names, vec!["grandkid 1 1", "child 2"]);
+    }
+
+    #[test]
+    fn depth_counts_levels() {
+        assert_eq!(tree().depth(), 3);
+        assert_eq!(Node::singleton("leaf".to_string()).depth(), 1);
+    }
+
+    #[test]
+    fn find_matches_predicate() {
+        let haystack = tree();
+        let found = haystack.find(|n| n.name == "grandkid 1 1").unwrap();
+        assert_eq!(found.name, "grandkid 1 1");
+        assert!(haystack.find(|n| n.name == "nonexistent").is_none());
+    }
+
+    #[test]
+    fn map_names_preserves_shape() {
+        let shouted = tree().map_names(|n| n.to_uppercase());
+        assert_eq!(shouted.depth(), tree().depth());
+        assert_eq!(shouted.name, "PARENT");
+        assert_eq!(shouted.children[0].name, "CHILD 1");
+    }
+
+    #[test]
+    fn map_names_handles_deep_trees() {
+        // `map_names` recurses once per level of the tree; a tree 500 levels
+        // deep still maps with its shape and names intact.
+        let mut deepest = Node::singleton("leaf".to_string());
+        for i in 0..500 {
+            deepest = Node { name: format!("node {}", i), children: Rc::from(vec![deepest]) };
+        }
+
+        let mapped = deepest.map_names(|n| n.to_uppercase());
+        assert_eq!(mapped.depth(), deepest.depth());
+        assert_eq!(mapped.name, "NODE 499");
+    }
+}
@@ -0,0 +1,46 @@
+use crate::Indent;
+
+/// The connector glyphs used to render a tree, plus the separator placed
+/// between a connector and whatever follows it (another connector, or the
+/// node name). Swap in [`Style::unicode`] for box-drawing output, or build
+/// a custom one for arbitrary glyphs.
+pub struct Style {
+    pub blank: String,
+    pub uplink: String,
+    pub split: String,
+    pub last: String,
+    pub separator: String,
+}
+
+impl Style {
+    /// The original plain-ASCII glyphs (`+--`, `` `-- ``, `|  `, `   `).
+    pub fn ascii() -> Style {
+        Style {
+            blank: "   ".to_string(),
+            uplink: "|  ".to_string(),
+            split: "+--".to_string(),
+            last: "`--".to_string(),
+            separator: " ".to_string(),
+        }
+    }
+
+    /// Unicode box-drawing glyphs, as produced by modern `tree`-like tools.
+    pub fn unicode() -> Style {
+        Style {
+            blank: "   ".to_string(),
+            uplink: "│  ".to_string(),
+            split: "├──".to_string(),
+            last: "└──".to_string(),
+            separator: " ".to_string(),
+        }
+    }
+
+    pub(crate) fn connector(&self, indent: Indent) -> &str {
+        match indent {
+            Indent::Blank  => &self.blank,
+            Indent::Uplink => &self.uplink,
+            Indent::Split  => &self.split,
+            Indent::Last   => &self.last,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::{Node, NodeCache, TreeBuilder};
+
+/// A stable (volume, file) identifier used to detect symlink cycles, or
+/// `None` on platforms with no portable way to obtain one — on those,
+/// cycle detection is simply unavailable and `follow_symlinks` can loop
+/// on a self-referential symlink.
+#[cfg(unix)]
+fn file_identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(windows)]
+fn file_identity(metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    use std::os::windows::fs::MetadataExt;
+    match (metadata.volume_serial_number(), metadata.file_index()) {
+        (Some(volume), Some(index)) => Some((volume as u64, index)),
+        _ => None,
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn file_identity(_metadata: &fs::Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// How a directory's entries are ordered before being visited.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum SortMode {
+    /// Whatever order the OS hands back from `read_dir` (the `from_path` default).
+    None,
+    ByName,
+    /// Directories first, then files; alphabetical within each group.
+    DirsFirst,
+}
+
+/// Controls for [`Node::from_path_with`]: ordering, how deep to descend, and
+/// how to treat symlinks and dotfiles.
+pub struct WalkOptions {
+    pub sort: SortMode,
+    pub max_depth: Option<usize>,
+    /// Descend into symlinked directories. Off by default; when on, a
+    /// visited-file set guards against symlink cycles on platforms where a
+    /// stable file identity is available (Unix, Windows).
+    pub follow_symlinks: bool,
+    pub include_hidden: bool,
+}
+
+impl Default for WalkOptions {
+    fn default() -> WalkOptions {
+        WalkOptions {
+            sort: SortMode::None,
+            max_depth: None,
+            follow_symlinks: false,
+            include_hidden: true,
+        }
+    }
+}
+
+impl Node {
+    /// Like [`Node::from_path`], but with explicit control over ordering,
+    /// depth, symlinks, and hidden entries via [`WalkOptions`].
+    pub fn from_path_with(path: &Path, options: &WalkOptions) -> io::Result<Node> {
+        let cache = NodeCache::new();
+        let mut builder = TreeBuilder::new(&cache);
+        let mut visited = HashSet::new();
+        let name = path.display().to_string();
+
+        walk(path, &name, 0, options, &mut visited, &mut builder)?;
+
+        Ok(builder.build())
+    }
+}
+
+fn walk(
+    path: &Path,
+    name: &str,
+    depth: usize,
+    options: &WalkOptions,
+    visited: &mut HashSet<(u64, u64)>,
+    builder: &mut TreeBuilder,
+) -> io::Result<()> {
+    builder.start_node(name);
+
+    let within_depth = options.max_depth.is_none_or(|max| depth < max);
+
+    if within_depth && path.is_dir() {
+        let is_symlink = fs::symlink_metadata(path)?.file_type().is_symlink();
+
+        // A cycle can only arise by following a symlink back onto one of its
+        // own ancestors, so only symlinked directories need the visited check.
+        let (descend, inserted_key) = if !is_symlink {
+            (true, None)
+        }
+        else if !options.follow_symlinks {
+            (false, None)
+        }
+        else {
+            let metadata = fs::metadata(path)?;
+            match file_identity(&metadata) {
+                Some(key) => (visited.insert(key), Some(key)),
+                None => (true, None),
+            }
+        };
+
+        if descend {
+            let mut entries: Vec<fs::DirEntry> = fs::read_dir(path)?.collect::<io::Result<_>>()?;
+
+            entries.retain(|entry| {
+                options.include_hidden || !entry.file_name().to_string_lossy().starts_with('.')
+            });
+
+            match options.sort {
+                SortMode::None => {}
+                SortMode::ByName => entries.sort_by_key(|entry| entry.file_name()),
+                SortMode::DirsFirst => entries.sort_by(|a, b| {
+                    let a_is_dir = a.path().is_dir();
+                    let b_is_dir = b.path().is_dir();
+                    b_is_dir.cmp(&a_is_dir).then_with(|| a.file_name().cmp(&b.file_name()))
+                }),
+            }
+
+            for entry in entries {
+                let child_path = entry.path();
+                let child_name = entry.file_name().to_string_lossy().to_string();
+                walk(&child_path, &child_name, depth + 1, options, visited, builder)?;
+            }
+
+            if let Some(key) = inserted_key {
+                visited.remove(&key);
+            }
+        }
+    }
+
+    builder.finish_node();
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+
+    fn write(path: &Path) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        File::create(path).unwrap();
+    }
+
+    #[test]
+    fn sorts_by_name() {
+        let dir = std::env::temp_dir().join("oxydendrum_walk_sort");
+        let _ = fs::remove_dir_all(&dir);
+        write(&dir.join("b.txt"));
+        write(&dir.join("a.txt"));
+
+        let options = WalkOptions { sort: SortMode::ByName, ..WalkOptions::default() };
+        let tree = Node::from_path_with(&dir, &options).unwrap();
+
+        let names: Vec<_> = tree.children.iter().map(|c| c.name.clone()).collect();
+        assert_eq!(names, vec!["a.txt", "b.txt"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn respects_max_depth() {
+        let dir = std::env::temp_dir().join("oxydendrum_walk_depth");
+        let _ = fs::remove_dir_all(&dir);
+        write(&dir.join("nested/deep.txt"));
+
+        let options = WalkOptions { max_depth: Some(1), ..WalkOptions::default() };
+        let tree = Node::from_path_with(&dir, &options).unwrap();
+
+        let nested = tree.children.iter().find(|c| c.name == "nested").unwrap();
+        assert!(nested.children.is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn hides_dotfiles_unless_asked() {
+        let dir = std::env::temp_dir().join("oxydendrum_walk_hidden");
+        let _ = fs::remove_dir_all(&dir);
+        write(&dir.join(".hidden"));
+        write(&dir.join("visible.txt"));
+
+        let hidden_excluded = WalkOptions { include_hidden: false, ..WalkOptions::default() };
+        let tree = Node::from_path_with(&dir, &hidden_excluded).unwrap();
+        assert_eq!(tree.children.len(), 1);
+        assert_eq!(tree.children[0].name, "visible.txt");
+
+        let tree = Node::from_path_with(&dir, &WalkOptions::default()).unwrap();
+        assert_eq!(tree.children.len(), 2);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}